@@ -1,7 +1,10 @@
+use std::collections::{HashMap, VecDeque};
 use std::io;
+use std::time::{Duration, Instant};
 
+use crate::cli::{Cli, TemperatureType};
 use crate::tui;
-use ratatui::widgets::{self, Gauge};
+use ratatui::widgets::{self, Axis, Chart, Dataset, GraphType, Gauge, Tabs, TableState};
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
@@ -15,12 +18,79 @@ use ratatui::{
     widgets::{Block, Paragraph, Row, Widget},
     Frame,
 };
-use sysinfo::System;
+use sysinfo::{Components, Disks, Networks, Pid, System};
+
+/// How many seconds of samples the history graphs keep around before the
+/// oldest points are evicted.
+const HISTORY_RETENTION_SECS: f64 = 60.0;
+
+/// How long a kill confirmation stays on screen before it's cleared.
+const KILL_MESSAGE_TTL_SECS: f64 = 3.0;
+
+/// Rows to jump per PageUp/PageDown press in the process table.
+const PROCESS_PAGE_JUMP: usize = 10;
+
+/// Largest the per-core CPU panel is allowed to grow before it scrolls
+/// instead of pushing other Overview widgets off screen.
+const MAX_CPU_PANEL_ROWS: u16 = 15;
+
+/// The tabbed pages the UI can be on. `App::curr` indexes into this list.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Page {
+    Overview,
+    Processes,
+    Network,
+    Disks,
+    Temperature,
+}
+
+impl Page {
+    const ALL: [Page; 5] = [
+        Page::Overview,
+        Page::Processes,
+        Page::Network,
+        Page::Disks,
+        Page::Temperature,
+    ];
+
+    fn from_index(index: usize) -> Page {
+        Page::ALL[index % Page::ALL.len()]
+    }
+
+    fn titles() -> Vec<&'static str> {
+        vec!["Overview", "Processes", "Network", "Disks", "Temperature"]
+    }
+}
 
-#[derive(Clone, Copy)]
 pub struct App {
     exit: bool,
     curr: usize,
+    sys: System,
+    disks: Disks,
+    networks: Networks,
+    start: Instant,
+    retention_secs: f64,
+    cpu_history: VecDeque<(f64, f64)>,
+    mem_history: VecDeque<(f64, f64)>,
+    /// `(total_received, total_transmitted)` per interface as of the last
+    /// time the Network page was drawn, used together with `prev_network_time` to turn sysinfo's
+    /// cumulative byte counters into a throughput rate.
+    prev_network: HashMap<String, (u64, u64)>,
+    prev_network_time: f64,
+    /// PIDs sorted by memory usage, retained across frames so the selected
+    /// row stays on the same process even as other processes come and go.
+    sorted_pids: Vec<Pid>,
+    process_table_state: TableState,
+    kill_message: Option<(String, f64)>,
+    /// When `true`, the Overview page shows one gauge per logical CPU
+    /// instead of a single averaged gauge.
+    show_per_core: bool,
+    /// First core index shown in the per-core view, for scrolling past the
+    /// panel's fixed height on high core-count machines.
+    cpu_scroll: usize,
+    components: Components,
+    temperature_type: TemperatureType,
+    tick_rate_ms: u64,
 }
 
 impl AsMut<App> for App {
@@ -34,24 +104,301 @@ impl<'a> Default for App {
         Self {
             exit: false,
             curr: 0,
+            // Please note that we use "new_all" once at startup to ensure
+            // that the list of users and processes is already filled! Every
+            // later tick only refreshes the specific subsystems we actually
+            // display. Disks and network interfaces live on their own
+            // sysinfo types since they're refreshed independently.
+            sys: System::new_all(),
+            disks: Disks::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+            start: Instant::now(),
+            retention_secs: HISTORY_RETENTION_SECS,
+            cpu_history: VecDeque::new(),
+            mem_history: VecDeque::new(),
+            prev_network: HashMap::new(),
+            prev_network_time: 0.0,
+            sorted_pids: Vec::new(),
+            process_table_state: TableState::default(),
+            kill_message: None,
+            show_per_core: false,
+            cpu_scroll: 0,
+            components: Components::new_with_refreshed_list(),
+            temperature_type: TemperatureType::Celsius,
+            tick_rate_ms: 1000,
+        }
+    }
+}
+
+impl App {
+    /// Builds an `App` configured from parsed command-line options.
+    pub fn new(cli: Cli) -> Self {
+        Self {
+            temperature_type: cli.temperature_type,
+            tick_rate_ms: cli.tick_rate_ms,
+            ..Self::default()
+        }
+    }
+
+    /// Pushes a new `(timestamp, percent)` sample onto `history` and evicts
+    /// anything older than `retention_secs`.
+    fn push_sample(history: &mut VecDeque<(f64, f64)>, now: f64, percent: f64, retention_secs: f64) {
+        history.push_back((now, percent));
+        while let Some((t, _)) = history.front() {
+            if now - t > retention_secs {
+                history.pop_front();
+            } else {
+                break;
+            }
         }
     }
 }
 
-impl<'a> Widget for App {
-    fn render(self, area: Rect, buf: &mut Buffer)
-    where
-        Self: Sized,
-    {
-        // Please note that we use "new_all" to ensure that all list of
-        // components, network interfaces, disks and users are already
-        // filled!
-        let mut sys = System::new_all();
-
-        // First we update all information of our `System` struct.
-        sys.refresh_all();
+impl App {
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let page = Page::from_index(self.curr);
+
+        self.sys.refresh_cpu();
+        self.sys.refresh_memory();
+        self.sys.refresh_processes();
+        if page == Page::Disks {
+            self.disks.refresh_list();
+        }
+        if page == Page::Network {
+            self.networks.refresh_list();
+            self.networks.refresh();
+        }
+        if page == Page::Temperature {
+            self.components.refresh_list();
+            self.components.refresh();
+        }
+
+        let selected_pid = self
+            .process_table_state
+            .selected()
+            .and_then(|index| self.sorted_pids.get(index).copied());
+        let mut sorted: Vec<(&Pid, &sysinfo::Process)> = self.sys.processes().iter().collect();
+        sorted.sort_unstable_by_key(|(_, process)| std::cmp::Reverse(process.memory()));
+        self.sorted_pids = sorted.into_iter().map(|(pid, _)| *pid).collect();
+        if let Some(index) = selected_pid.and_then(|pid| self.sorted_pids.iter().position(|p| *p == pid)) {
+            self.process_table_state.select(Some(index));
+        }
+        if let Some(selected) = self.process_table_state.selected() {
+            if self.sorted_pids.is_empty() {
+                self.process_table_state.select(None);
+            } else if selected >= self.sorted_pids.len() {
+                self.process_table_state.select(Some(self.sorted_pids.len() - 1));
+            }
+        }
+
+        let now = self.start.elapsed().as_secs_f64();
+        if let Some((_, set_at)) = &self.kill_message {
+            if now - set_at > KILL_MESSAGE_TTL_SECS {
+                self.kill_message = None;
+            }
+        }
+        let usage = self.sys.global_cpu_info().cpu_usage();
+        let mem_percent = 100.0 * (self.sys.used_memory() as f64 / self.sys.total_memory() as f64);
+        App::push_sample(&mut self.cpu_history, now, usage as f64, self.retention_secs);
+        App::push_sample(&mut self.mem_history, now, mem_percent, self.retention_secs);
+
+        let outer = Layout::new(
+            Direction::Vertical,
+            vec![Constraint::Max(3), Constraint::Fill(100)],
+        )
+        .split(area);
+
+        let tabs = Tabs::new(Page::titles())
+            .block(Block::bordered().title("supermarket"))
+            .select(self.curr)
+            .highlight_style(Style::new().red().bold());
+        tabs.render(outer[0], buf);
+
+        match page {
+            Page::Overview => self.render_overview(outer[1], buf),
+            Page::Processes => self.render_processes(outer[1], buf),
+            Page::Network => self.render_network(now, outer[1], buf),
+            Page::Disks => self.render_disks(outer[1], buf),
+            Page::Temperature => self.render_temperatures(outer[1], buf),
+        }
+    }
+
+    fn render_temperatures(&self, area: Rect, buf: &mut Buffer) {
+        let unit = self.temperature_type.suffix();
+        let mut rows: Vec<Row> = vec![Row::new(vec!["Sensor", "Current", "Critical"])
+            .black()
+            .on_red()];
+        for component in self.components.list() {
+            let current = self.temperature_type.convert(component.temperature());
+            let critical = component
+                .critical()
+                .map(|c| format!("{:.1}{unit}", self.temperature_type.convert(c)))
+                .unwrap_or_else(|| "n/a".to_string());
+            rows.push(Row::new(vec![
+                component.label().to_string(),
+                format!("{current:.1}{unit}"),
+                critical,
+            ]));
+        }
+
+        let table = widgets::Table::new(
+            rows,
+            vec![
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ],
+        )
+        .block(Block::bordered().title("Temperature").on_black());
+        table.render(area, buf);
+    }
+
+    fn render_disks(&self, area: Rect, buf: &mut Buffer) {
+        let constraints: Vec<Constraint> = self.disks.list().iter().map(|_| Constraint::Max(3)).collect();
+        let rows = Layout::new(Direction::Vertical, constraints.clone())
+            .split(Block::bordered().title("Disks").on_black().inner(area));
+        Block::bordered().title("Disks").on_black().render(area, buf);
+
+        for (disk, row) in self.disks.list().iter().zip(rows.iter()) {
+            let used_space = disk.total_space().saturating_sub(disk.available_space());
+            let percent_used = if disk.total_space() == 0 {
+                0
+            } else {
+                ((100 * used_space / disk.total_space()) as u16).min(100)
+            };
+            let gauge = Gauge::default()
+                .percent(percent_used)
+                .gauge_style(Style::new().red())
+                .use_unicode(true)
+                .label(format!(
+                    "{} ({}) {:.1}/{:.1} GB",
+                    disk.mount_point().display(),
+                    disk.file_system().to_string_lossy(),
+                    used_space as f64 / (2.0_f64).powf(30.0),
+                    disk.total_space() as f64 / (2.0_f64).powf(30.0),
+                ))
+                .block(Block::default());
+            gauge.render(*row, buf);
+        }
+    }
+
+    fn render_network(&mut self, now: f64, area: Rect, buf: &mut Buffer) {
+        let elapsed = (now - self.prev_network_time).max(f64::EPSILON);
+
+        let mut rows: Vec<Row> = vec![Row::new(vec!["Interface", "Received/s", "Transmitted/s"])
+            .black()
+            .on_red()];
+        for (name, data) in self.networks.list() {
+            let (prev_rx, prev_tx) = self
+                .prev_network
+                .get(name)
+                .copied()
+                .unwrap_or((data.total_received(), data.total_transmitted()));
+            let rx_rate = (data.total_received().saturating_sub(prev_rx)) as f64 / elapsed;
+            let tx_rate = (data.total_transmitted().saturating_sub(prev_tx)) as f64 / elapsed;
+            rows.push(Row::new(vec![
+                name.clone(),
+                format!("{:.1} KB/s", rx_rate / 1024.0),
+                format!("{:.1} KB/s", tx_rate / 1024.0),
+            ]));
+        }
+
+        self.prev_network = self
+            .networks
+            .list()
+            .iter()
+            .map(|(name, data)| (name.clone(), (data.total_received(), data.total_transmitted())))
+            .collect();
+        self.prev_network_time = now;
+
+        let table = widgets::Table::new(
+            rows,
+            vec![
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ],
+        )
+        .block(Block::bordered().title("Network").on_black());
+        table.render(area, buf);
+    }
+
+    fn render_processes(&mut self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::new(
+            Direction::Vertical,
+            vec![Constraint::Fill(100), Constraint::Max(1)],
+        )
+        .split(area);
+
+        let sys = &self.sys;
+        let mut rows: Vec<Row> = vec![];
+        for (i, pid) in self.sorted_pids.iter().enumerate() {
+            let Some(process) = sys.process(*pid) else {
+                continue;
+            };
+            rows.push(Row::new(vec![
+                process.name().to_string(),
+                pid.to_string(),
+                format!("{}%", 100 * process.memory() / sys.total_memory()),
+            ]));
+            if i % 2 == 0 {
+                let last = rows.len() - 1;
+                rows[last] = rows[last].clone().on_black();
+            }
+        }
+
+        let table = widgets::Table::new(
+            rows,
+            vec![
+                Constraint::Percentage(50),
+                Constraint::Percentage(20),
+                Constraint::Percentage(30),
+            ],
+        )
+        .header(Row::new(vec!["Name", "PID", "Memory Usage"]).black().on_red())
+        .block(
+            Block::bordered()
+                .title("Processes (Up/Down select, PgUp/PgDn jump, k to kill)")
+                .on_black(),
+        )
+        .highlight_style(Style::new().black().on_red());
+        widgets::StatefulWidget::render(table, layout[0], buf, &mut self.process_table_state);
+
+        let status = match &self.kill_message {
+            Some((message, _)) => message.clone(),
+            None => String::new(),
+        };
+        Paragraph::new(status).red().render(layout[1], buf);
+    }
+
+    /// Sends `SIGKILL` to the currently selected process and records a
+    /// confirmation message to show for a few seconds.
+    fn kill_selected_process(&mut self, now: f64) {
+        let Some(selected) = self.process_table_state.selected() else {
+            return;
+        };
+        let Some(pid) = self.sorted_pids.get(selected).copied() else {
+            return;
+        };
+        let message = match self.sys.process(pid) {
+            Some(process) if process.kill() => format!("Killed {} (pid {pid})", process.name()),
+            Some(_) => format!("Failed to kill pid {pid}"),
+            None => format!("Process {pid} no longer exists"),
+        };
+        self.kill_message = Some((message, now));
+    }
+
+    fn render_overview(&self, area: Rect, buf: &mut Buffer) {
+        let now = self.start.elapsed().as_secs_f64();
+        let sys = &self.sys;
         let usage = sys.global_cpu_info().cpu_usage();
 
+        let cpu_height = if self.show_per_core {
+            (sys.cpus().len() as u16 + 2).clamp(3, MAX_CPU_PANEL_ROWS)
+        } else {
+            3
+        };
+
         let layout = Layout::new(
             Direction::Horizontal,
             vec![Constraint::Percentage(50), Constraint::Percentage(50)],
@@ -61,8 +408,9 @@ impl<'a> Widget for App {
             Direction::Vertical,
             vec![
                 Constraint::Max(3),
-                Constraint::Max(3),
+                Constraint::Max(cpu_height),
                 Constraint::Max(13),
+                Constraint::Max(10),
                 Constraint::Fill(100),
             ],
         )
@@ -79,34 +427,55 @@ impl<'a> Widget for App {
             .block(Block::bordered().title("Used Memory").on_black());
         memory_bar.render(right_side[0], buf);
 
-        let usage_bar = Gauge::default()
-            .percent((usage) as u16)
-            .gauge_style(Style::new().red())
-            .use_unicode(true)
-            .label(format!("{}%", (usage.round())))
-            .block(Block::bordered().title("CPU Usage").on_black());
-        usage_bar.render(right_side[1], buf);
-
-        let mut procs: Vec<Constraint> = vec![];
-        let mut i = 0;
-        let mut sorted: Vec<(&sysinfo::Pid, &sysinfo::Process)> = sys.processes().iter().collect();
-        sorted.sort_unstable_by(|a, b| (b.1.memory()).cmp(&a.1.memory()));
+        if self.show_per_core {
+            let cpu_block = Block::bordered()
+                .title("CPU Usage (per core, c to toggle, Up/Down to scroll)")
+                .on_black();
+            let inner = cpu_block.inner(right_side[1]);
+            cpu_block.render(right_side[1], buf);
+
+            let visible_rows = inner.height as usize;
+            let max_scroll = sys.cpus().len().saturating_sub(visible_rows);
+            let scroll = self.cpu_scroll.min(max_scroll);
+
+            let core_rows = Layout::new(
+                Direction::Vertical,
+                vec![Constraint::Max(1); visible_rows],
+            )
+            .split(inner);
+            for (row, (i, core)) in core_rows.iter().zip(sys.cpus().iter().enumerate().skip(scroll)) {
+                let core_usage = core.cpu_usage();
+                Gauge::default()
+                    .percent(core_usage as u16)
+                    .gauge_style(Style::new().red())
+                    .use_unicode(true)
+                    .label(format!("CPU{i}: {:.0}%", core_usage))
+                    .render(*row, buf);
+            }
+        } else {
+            let usage_bar = Gauge::default()
+                .percent((usage) as u16)
+                .gauge_style(Style::new().red())
+                .use_unicode(true)
+                .label(format!("{}%", (usage.round())))
+                .block(Block::bordered().title("CPU Usage (c for per-core)").on_black());
+            usage_bar.render(right_side[1], buf);
+        }
+
         let mut rows: Vec<Row> = vec![];
         rows.push(Row::new(vec!["Name", "Memory Usage"]).black().on_red());
-        for (_, process) in sorted {
-            if i >= 10 {
-                break;
-            }
-            procs.push(Constraint::Max(3));
+        for (i, pid) in self.sorted_pids.iter().take(10).enumerate() {
+            let Some(process) = sys.process(*pid) else {
+                continue;
+            };
             rows.push(Row::new(vec![
                 process.name().to_string(),
                 format!("{}%", 100 * process.memory() / sys.total_memory()),
             ]));
-            match i % 2 {
-                0 => rows[i + 1] = rows[i + 1].clone().on_black(),
-                _ => {}
+            if i % 2 == 0 {
+                let last = rows.len() - 1;
+                rows[last] = rows[last].clone().on_black();
             }
-            i += 1;
         }
 
         let table = widgets::Table::new(
@@ -116,6 +485,32 @@ impl<'a> Widget for App {
         .block(Block::bordered().title("Processes").on_black());
         table.render(right_side[2], buf);
 
+        let cpu_points: Vec<(f64, f64)> = self.cpu_history.iter().copied().collect();
+        let mem_points: Vec<(f64, f64)> = self.mem_history.iter().copied().collect();
+        let x_max = now.max(self.retention_secs);
+        let x_min = x_max - self.retention_secs;
+        let datasets = vec![
+            Dataset::default()
+                .name("CPU %")
+                .graph_type(GraphType::Line)
+                .style(Style::new().red())
+                .data(&cpu_points),
+            Dataset::default()
+                .name("Mem %")
+                .graph_type(GraphType::Line)
+                .style(Style::new().yellow())
+                .data(&mem_points),
+        ];
+        let history_chart = Chart::new(datasets)
+            .block(Block::bordered().title("History").on_black())
+            .x_axis(Axis::default().bounds([x_min, x_max]))
+            .y_axis(
+                Axis::default()
+                    .bounds([0.0, 100.0])
+                    .labels(vec!["0", "50", "100"]),
+            );
+        history_chart.render(right_side[3], buf);
+
         let left_side = Layout::new(
             Direction::Vertical,
             vec![Constraint::Max(6), Constraint::Fill(100)],
@@ -162,19 +557,31 @@ impl<'a> Widget for App {
             .on_black();
         spec_table.render(left_side[1], buf);
     }
-}
 
-impl App {
     pub fn run(&mut self, terminal: &mut tui::Tui) -> io::Result<()> {
+        let tick_rate = Duration::from_millis(self.tick_rate_ms);
+        let mut last_tick = Instant::now();
         while !self.exit {
-            terminal.draw(|frame| self.render_frame(frame))?;
-            self.handle_events()?;
+            let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+            if self.poll_events(timeout)? {
+                self.handle_events()?;
+            }
+            if last_tick.elapsed() >= tick_rate {
+                terminal.draw(|frame| self.render_frame(frame))?;
+                last_tick = Instant::now();
+            }
         }
         Ok(())
     }
 
-    pub fn render_frame(&self, frame: &mut Frame) {
-        frame.render_widget(*self, frame.size());
+    pub fn render_frame(&mut self, frame: &mut Frame) {
+        let area = frame.size();
+        self.render(area, frame.buffer_mut());
+    }
+
+    /// Waits up to `timeout` for a terminal event to become available.
+    fn poll_events(&self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
     }
 
     fn handle_events(&mut self) -> io::Result<()> {
@@ -190,12 +597,75 @@ impl App {
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match (key_event.code, self.curr) {
+        let page = Page::from_index(self.curr);
+        match (key_event.code, page) {
             (KeyCode::Char('q'), _) => self.exit = true,
-            (KeyCode::Left, _) => self.curr = (self.curr + 2) % 3,
-            (KeyCode::Right, _) => self.curr = (self.curr + 1) % 3,
+            (KeyCode::Left, _) => self.curr = (self.curr + Page::ALL.len() - 1) % Page::ALL.len(),
+            (KeyCode::Right, _) => self.curr = (self.curr + 1) % Page::ALL.len(),
+
+            (KeyCode::Up, Page::Processes) => self.move_process_selection(-1),
+            (KeyCode::Down, Page::Processes) => self.move_process_selection(1),
+            (KeyCode::Up, Page::Overview) if self.show_per_core => {
+                self.cpu_scroll = self.cpu_scroll.saturating_sub(1)
+            }
+            (KeyCode::Down, Page::Overview) if self.show_per_core => self.cpu_scroll += 1,
+            (KeyCode::PageUp, Page::Processes) => {
+                self.move_process_selection(-(PROCESS_PAGE_JUMP as isize))
+            }
+            (KeyCode::PageDown, Page::Processes) => {
+                self.move_process_selection(PROCESS_PAGE_JUMP as isize)
+            }
+            (KeyCode::Char('k'), Page::Processes) => {
+                self.kill_selected_process(self.start.elapsed().as_secs_f64())
+            }
+            (KeyCode::Char('c'), _) => self.show_per_core = !self.show_per_core,
 
             _ => {}
         }
     }
+
+    /// Moves the process table selection by `delta` rows, clamped to the
+    /// bounds of `sorted_pids`.
+    fn move_process_selection(&mut self, delta: isize) {
+        if self.sorted_pids.is_empty() {
+            self.process_table_state.select(None);
+            return;
+        }
+        let last = self.sorted_pids.len() as isize - 1;
+        let next = match self.process_table_state.selected() {
+            Some(current) => (current as isize + delta).clamp(0, last),
+            None => 0,
+        };
+        self.process_table_state.select(Some(next as usize));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::App;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn push_sample_keeps_recent_samples() {
+        let mut history = VecDeque::new();
+        App::push_sample(&mut history, 0.0, 10.0, 60.0);
+        App::push_sample(&mut history, 30.0, 20.0, 60.0);
+        App::push_sample(&mut history, 59.0, 30.0, 60.0);
+        assert_eq!(
+            history.into_iter().collect::<Vec<_>>(),
+            vec![(0.0, 10.0), (30.0, 20.0), (59.0, 30.0)]
+        );
+    }
+
+    #[test]
+    fn push_sample_evicts_samples_older_than_retention() {
+        let mut history = VecDeque::new();
+        App::push_sample(&mut history, 0.0, 10.0, 60.0);
+        App::push_sample(&mut history, 30.0, 20.0, 60.0);
+        App::push_sample(&mut history, 61.0, 30.0, 60.0);
+        assert_eq!(
+            history.into_iter().collect::<Vec<_>>(),
+            vec![(30.0, 20.0), (61.0, 30.0)]
+        );
+    }
 }
@@ -0,0 +1,55 @@
+use clap::{Parser, ValueEnum};
+
+/// Command-line options for `supermarket`.
+#[derive(Parser)]
+#[command(author, version, about)]
+pub struct Cli {
+    /// How often the UI refreshes and polls for input, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    pub tick_rate_ms: u64,
+
+    /// Unit to display sensor temperatures in.
+    #[arg(long, value_enum, default_value_t = TemperatureType::Celsius)]
+    pub temperature_type: TemperatureType,
+}
+
+/// Unit used to display component temperatures, mirroring bottom's
+/// `TemperatureType`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TemperatureType {
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureType {
+    /// Converts a Celsius reading from sysinfo into this unit.
+    pub fn convert(self, celsius: f32) -> f32 {
+        match self {
+            TemperatureType::Celsius => celsius,
+            TemperatureType::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            TemperatureType::Celsius => "C",
+            TemperatureType::Fahrenheit => "F",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TemperatureType;
+
+    #[test]
+    fn celsius_passes_through_unchanged() {
+        assert_eq!(TemperatureType::Celsius.convert(36.6), 36.6);
+    }
+
+    #[test]
+    fn celsius_converts_to_fahrenheit() {
+        assert_eq!(TemperatureType::Fahrenheit.convert(100.0), 212.0);
+        assert_eq!(TemperatureType::Fahrenheit.convert(0.0), 32.0);
+    }
+}